@@ -0,0 +1,144 @@
+//! Threshold and statistical anomaly alerting over a fetched price series.
+//!
+//! Turns the raw `Vec<StockData>` series into a list of actionable `Alert`s:
+//! simple upper/lower price-threshold breaches, plus a rolling z-score
+//! anomaly detector that flags points deviating sharply from their trailing
+//! mean.
+
+use crate::StockData;
+
+/// How urgent an `Alert` is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Severity {
+    Warning,
+    Critical,
+}
+
+/// What condition triggered an `Alert`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AlertKind {
+    ThresholdBreach,
+    Anomaly,
+}
+
+/// A single detected event on a price series.
+#[derive(Debug, Clone)]
+pub(crate) struct Alert {
+    pub(crate) index: usize,
+    pub(crate) timestamp: String,
+    pub(crate) symbol: String,
+    pub(crate) value: f64,
+    pub(crate) kind: AlertKind,
+    pub(crate) severity: Severity,
+}
+
+/// Upper/lower price bounds to alert on.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Thresholds {
+    pub(crate) upper: Option<f64>,
+    pub(crate) lower: Option<f64>,
+}
+
+/// Flag points in `data` whose price is at or above `thresholds.upper` or at
+/// or below `thresholds.lower`.
+pub(crate) fn check_thresholds(data: &[StockData], thresholds: Thresholds) -> Vec<Alert> {
+    data.iter()
+        .enumerate()
+        .filter_map(|(index, point)| {
+            if thresholds.upper.is_some_and(|upper| point.price >= upper) || thresholds.lower.is_some_and(|lower| point.price <= lower) {
+                Some(Alert {
+                    index,
+                    timestamp: point.timestamp.clone(),
+                    symbol: point.symbol.clone(),
+                    value: point.price,
+                    kind: AlertKind::ThresholdBreach,
+                    severity: Severity::Warning,
+                })
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Flag points whose deviation from their trailing rolling mean is at least
+/// `z_threshold` standard deviations.
+///
+/// # Arguments
+///
+/// * `data` - The price series to scan.
+/// * `window` - The number of trailing samples used for the rolling mean/stddev.
+/// * `z_threshold` - How many standard deviations away from the mean counts as anomalous.
+pub(crate) fn detect_anomalies(data: &[StockData], window: usize, z_threshold: f64) -> Vec<Alert> {
+    let mut alerts = Vec::new();
+
+    for index in window..data.len() {
+        let trailing = &data[index - window..index];
+        let mean = trailing.iter().map(|p| p.price).sum::<f64>() / window as f64;
+        let variance = trailing.iter().map(|p| (p.price - mean).powi(2)).sum::<f64>() / window as f64;
+        let std_dev = variance.sqrt();
+        let point = &data[index];
+
+        // A perfectly flat trailing window has no z-score to compute, so any
+        // departure from it at all counts as the most severe anomaly.
+        let severity = if std_dev == 0.0 {
+            if point.price == mean {
+                continue;
+            }
+            Severity::Critical
+        } else {
+            let z_score = (point.price - mean) / std_dev;
+            if z_score.abs() < z_threshold {
+                continue;
+            }
+            if z_score.abs() >= z_threshold * 2.0 { Severity::Critical } else { Severity::Warning }
+        };
+
+        alerts.push(Alert {
+            index,
+            timestamp: point.timestamp.clone(),
+            symbol: point.symbol.clone(),
+            value: point.price,
+            kind: AlertKind::Anomaly,
+            severity,
+        });
+    }
+
+    alerts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(price: f64) -> StockData {
+        StockData {
+            symbol: "IBM".to_string(),
+            price,
+            volume: 0,
+            timestamp: "t".to_string(),
+            open: None,
+            high: None,
+            low: None,
+            close: None,
+        }
+    }
+
+    #[test]
+    fn threshold_breach_is_flagged() {
+        let data = vec![point(10.0), point(20.0), point(5.0)];
+        let alerts = check_thresholds(&data, Thresholds { upper: Some(15.0), lower: Some(6.0) });
+        assert_eq!(alerts.len(), 2);
+        assert_eq!(alerts[0].index, 1);
+        assert_eq!(alerts[1].index, 2);
+    }
+
+    #[test]
+    fn anomaly_detector_flags_outlier() {
+        let mut data: Vec<StockData> = (0..10).map(|_| point(100.0)).collect();
+        data.push(point(500.0));
+        let alerts = detect_anomalies(&data, 5, 2.0);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].index, 10);
+    }
+}
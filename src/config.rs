@@ -0,0 +1,275 @@
+//! Run configuration and the structured JSON report emitted on completion.
+//!
+//! Configuration is loaded from a JSON file and layered with environment
+//! variable overrides, so the same binary can be scripted into a larger
+//! workflow instead of only accepting a hard-coded API key and symbol.
+
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use crate::{features, CustomError};
+
+/// Configuration for a monitoring run, typically loaded from `config.json`.
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct Config {
+    pub(crate) api_key: String,
+    pub(crate) symbols: Vec<String>,
+    /// The Alpha Vantage `function` query parameter, e.g. `"TIME_SERIES_INTRADAY"`,
+    /// `"TIME_SERIES_DAILY"`, `"TIME_SERIES_WEEKLY"`, `"TIME_SERIES_MONTHLY"`.
+    #[serde(default = "default_function")]
+    pub(crate) function: String,
+    #[serde(default = "default_interval")]
+    pub(crate) interval: String,
+    #[serde(default = "default_model")]
+    pub(crate) model: String,
+    #[serde(default = "default_window_size")]
+    pub(crate) window_size: usize,
+    /// Upper price bound to alert on, applied to every symbol in the run.
+    #[serde(default)]
+    pub(crate) threshold_upper: Option<f64>,
+    /// Lower price bound to alert on, applied to every symbol in the run.
+    #[serde(default)]
+    pub(crate) threshold_lower: Option<f64>,
+    /// Trailing window size used by the rolling z-score anomaly detector.
+    #[serde(default = "default_anomaly_window")]
+    pub(crate) anomaly_window: usize,
+    /// Number of standard deviations from the rolling mean that counts as anomalous.
+    #[serde(default = "default_anomaly_z_threshold")]
+    pub(crate) anomaly_z_threshold: f64,
+    /// Seconds to sleep between polling passes over `symbols`.
+    #[serde(default = "default_poll_interval_secs")]
+    pub(crate) poll_interval_secs: u64,
+}
+
+fn default_function() -> String {
+    "TIME_SERIES_INTRADAY".to_string()
+}
+
+fn default_interval() -> String {
+    "1min".to_string()
+}
+
+fn default_model() -> String {
+    "gaussian-nb".to_string()
+}
+
+fn default_window_size() -> usize {
+    features::DEFAULT_WINDOW_SIZE
+}
+
+fn default_anomaly_window() -> usize {
+    20
+}
+
+fn default_anomaly_z_threshold() -> f64 {
+    3.0
+}
+
+fn default_poll_interval_secs() -> u64 {
+    60
+}
+
+impl Config {
+    /// Load configuration from the JSON file at `path`, then apply any
+    /// `STOCK_MONITOR_*` environment variable overrides.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to a JSON config file with `api_key` and `symbols` at minimum.
+    pub(crate) fn load(path: impl AsRef<Path>) -> Result<Self, CustomError> {
+        let contents = fs::read_to_string(path).map_err(|err| CustomError::ConfigError(err.to_string()))?;
+        let mut config: Config = serde_json::from_str(&contents)?;
+        config.apply_env_overrides();
+        Ok(config)
+    }
+
+    /// Overrides recognized: `STOCK_MONITOR_API_KEY`, `STOCK_MONITOR_SYMBOLS`
+    /// (comma-separated), `STOCK_MONITOR_FUNCTION`, `STOCK_MONITOR_INTERVAL`,
+    /// `STOCK_MONITOR_MODEL`, `STOCK_MONITOR_WINDOW_SIZE`,
+    /// `STOCK_MONITOR_THRESHOLD_UPPER`, `STOCK_MONITOR_THRESHOLD_LOWER`,
+    /// `STOCK_MONITOR_ANOMALY_WINDOW`, `STOCK_MONITOR_ANOMALY_Z_THRESHOLD`,
+    /// `STOCK_MONITOR_POLL_INTERVAL_SECS`.
+    fn apply_env_overrides(&mut self) {
+        if let Ok(api_key) = env::var("STOCK_MONITOR_API_KEY") {
+            self.api_key = api_key;
+        }
+        if let Ok(symbols) = env::var("STOCK_MONITOR_SYMBOLS") {
+            self.symbols = symbols.split(',').map(|s| s.trim().to_string()).collect();
+        }
+        if let Ok(function) = env::var("STOCK_MONITOR_FUNCTION") {
+            self.function = function;
+        }
+        if let Ok(interval) = env::var("STOCK_MONITOR_INTERVAL") {
+            self.interval = interval;
+        }
+        if let Ok(model) = env::var("STOCK_MONITOR_MODEL") {
+            self.model = model;
+        }
+        if let Ok(window_size) = env::var("STOCK_MONITOR_WINDOW_SIZE") {
+            if let Ok(parsed) = window_size.parse() {
+                self.window_size = parsed;
+            }
+        }
+        if let Ok(threshold_upper) = env::var("STOCK_MONITOR_THRESHOLD_UPPER") {
+            if let Ok(parsed) = threshold_upper.parse() {
+                self.threshold_upper = Some(parsed);
+            }
+        }
+        if let Ok(threshold_lower) = env::var("STOCK_MONITOR_THRESHOLD_LOWER") {
+            if let Ok(parsed) = threshold_lower.parse() {
+                self.threshold_lower = Some(parsed);
+            }
+        }
+        if let Ok(anomaly_window) = env::var("STOCK_MONITOR_ANOMALY_WINDOW") {
+            if let Ok(parsed) = anomaly_window.parse() {
+                self.anomaly_window = parsed;
+            }
+        }
+        if let Ok(anomaly_z_threshold) = env::var("STOCK_MONITOR_ANOMALY_Z_THRESHOLD") {
+            if let Ok(parsed) = anomaly_z_threshold.parse() {
+                self.anomaly_z_threshold = parsed;
+            }
+        }
+        if let Ok(poll_interval_secs) = env::var("STOCK_MONITOR_POLL_INTERVAL_SECS") {
+            if let Ok(parsed) = poll_interval_secs.parse() {
+                self.poll_interval_secs = parsed;
+            }
+        }
+    }
+}
+
+/// A machine-readable summary of one symbol's run.
+#[derive(Debug, Serialize)]
+pub(crate) struct SymbolReport {
+    pub(crate) symbol: String,
+    pub(crate) model: String,
+    pub(crate) accuracy_mean: f64,
+    pub(crate) accuracy_std_dev: f64,
+    pub(crate) latest_prediction: String,
+    pub(crate) plot_paths: Vec<String>,
+}
+
+/// Serialize `reports` as pretty JSON and write them to `path`.
+pub(crate) fn write_report(reports: &[SymbolReport], path: impl AsRef<Path>) -> Result<(), CustomError> {
+    let json = serde_json::to_string_pretty(reports)?;
+    fs::write(path, json).map_err(|err| CustomError::ConfigError(err.to_string()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_applies_env_overrides() {
+        let mut config = Config {
+            api_key: "placeholder".to_string(),
+            symbols: vec!["IBM".to_string()],
+            function: default_function(),
+            interval: default_interval(),
+            model: default_model(),
+            window_size: default_window_size(),
+            threshold_upper: None,
+            threshold_lower: None,
+            anomaly_window: default_anomaly_window(),
+            anomaly_z_threshold: default_anomaly_z_threshold(),
+            poll_interval_secs: default_poll_interval_secs(),
+        };
+        env::set_var("STOCK_MONITOR_SYMBOLS", "AAPL, MSFT");
+        config.apply_env_overrides();
+        env::remove_var("STOCK_MONITOR_SYMBOLS");
+        assert_eq!(config.symbols, vec!["AAPL".to_string(), "MSFT".to_string()]);
+    }
+
+    #[test]
+    fn config_applies_threshold_env_overrides() {
+        let mut config = Config {
+            api_key: "placeholder".to_string(),
+            symbols: vec!["IBM".to_string()],
+            function: default_function(),
+            interval: default_interval(),
+            model: default_model(),
+            window_size: default_window_size(),
+            threshold_upper: None,
+            threshold_lower: None,
+            anomaly_window: default_anomaly_window(),
+            anomaly_z_threshold: default_anomaly_z_threshold(),
+            poll_interval_secs: default_poll_interval_secs(),
+        };
+        env::set_var("STOCK_MONITOR_THRESHOLD_UPPER", "150.5");
+        env::set_var("STOCK_MONITOR_THRESHOLD_LOWER", "90.0");
+        config.apply_env_overrides();
+        env::remove_var("STOCK_MONITOR_THRESHOLD_UPPER");
+        env::remove_var("STOCK_MONITOR_THRESHOLD_LOWER");
+        assert_eq!(config.threshold_upper, Some(150.5));
+        assert_eq!(config.threshold_lower, Some(90.0));
+    }
+
+    #[test]
+    fn config_applies_function_env_override() {
+        let mut config = Config {
+            api_key: "placeholder".to_string(),
+            symbols: vec!["IBM".to_string()],
+            function: default_function(),
+            interval: default_interval(),
+            model: default_model(),
+            window_size: default_window_size(),
+            threshold_upper: None,
+            threshold_lower: None,
+            anomaly_window: default_anomaly_window(),
+            anomaly_z_threshold: default_anomaly_z_threshold(),
+            poll_interval_secs: default_poll_interval_secs(),
+        };
+        env::set_var("STOCK_MONITOR_FUNCTION", "TIME_SERIES_DAILY");
+        config.apply_env_overrides();
+        env::remove_var("STOCK_MONITOR_FUNCTION");
+        assert_eq!(config.function, "TIME_SERIES_DAILY");
+    }
+
+    #[test]
+    fn config_applies_anomaly_env_overrides() {
+        let mut config = Config {
+            api_key: "placeholder".to_string(),
+            symbols: vec!["IBM".to_string()],
+            function: default_function(),
+            interval: default_interval(),
+            model: default_model(),
+            window_size: default_window_size(),
+            threshold_upper: None,
+            threshold_lower: None,
+            anomaly_window: default_anomaly_window(),
+            anomaly_z_threshold: default_anomaly_z_threshold(),
+            poll_interval_secs: default_poll_interval_secs(),
+        };
+        env::set_var("STOCK_MONITOR_ANOMALY_WINDOW", "30");
+        env::set_var("STOCK_MONITOR_ANOMALY_Z_THRESHOLD", "2.5");
+        config.apply_env_overrides();
+        env::remove_var("STOCK_MONITOR_ANOMALY_WINDOW");
+        env::remove_var("STOCK_MONITOR_ANOMALY_Z_THRESHOLD");
+        assert_eq!(config.anomaly_window, 30);
+        assert_eq!(config.anomaly_z_threshold, 2.5);
+    }
+
+    #[test]
+    fn config_applies_poll_interval_env_override() {
+        let mut config = Config {
+            api_key: "placeholder".to_string(),
+            symbols: vec!["IBM".to_string()],
+            function: default_function(),
+            interval: default_interval(),
+            model: default_model(),
+            window_size: default_window_size(),
+            threshold_upper: None,
+            threshold_lower: None,
+            anomaly_window: default_anomaly_window(),
+            anomaly_z_threshold: default_anomaly_z_threshold(),
+            poll_interval_secs: default_poll_interval_secs(),
+        };
+        env::set_var("STOCK_MONITOR_POLL_INTERVAL_SECS", "120");
+        config.apply_env_overrides();
+        env::remove_var("STOCK_MONITOR_POLL_INTERVAL_SECS");
+        assert_eq!(config.poll_interval_secs, 120);
+    }
+}
@@ -0,0 +1,144 @@
+//! Market data providers.
+//!
+//! `DataSource` decouples the rest of the pipeline from any one vendor's
+//! response schema. `AlphaVantage` is the provider implemented today;
+//! adding another exchange or an offline/CSV source means adding a new
+//! `DataSource` impl here, not touching `fetch`/`preprocess_data` call sites.
+
+use crate::{CustomError, StockData};
+
+/// A source of stock data for a given symbol.
+#[async_trait::async_trait]
+pub(crate) trait DataSource {
+    /// Fetch the most recent series of `StockData` for `symbol`.
+    async fn fetch(&self, symbol: &str) -> Result<Vec<StockData>, CustomError>;
+}
+
+/// The Alpha Vantage REST API (<https://www.alphavantage.co>).
+pub(crate) struct AlphaVantage {
+    api_key: String,
+    /// The Alpha Vantage `function` query parameter, e.g. `"TIME_SERIES_INTRADAY"`.
+    function: String,
+    /// The sampling interval, e.g. `"1min"`, `"5min"`. Ignored for daily functions.
+    interval: String,
+}
+
+impl AlphaVantage {
+    pub(crate) fn new(api_key: impl Into<String>, function: impl Into<String>, interval: impl Into<String>) -> Self {
+        AlphaVantage {
+            api_key: api_key.into(),
+            function: function.into(),
+            interval: interval.into(),
+        }
+    }
+
+    fn url(&self, symbol: &str) -> String {
+        match self.function.as_str() {
+            "TIME_SERIES_INTRADAY" => format!(
+                "https://www.alphavantage.co/query?function={}&symbol={}&interval={}&apikey={}",
+                self.function, symbol, self.interval, self.api_key
+            ),
+            _ => format!(
+                "https://www.alphavantage.co/query?function={}&symbol={}&apikey={}",
+                self.function, symbol, self.api_key
+            ),
+        }
+    }
+
+    /// The JSON key under which Alpha Vantage nests the time series, which
+    /// depends on which `function`/`interval` was requested.
+    fn time_series_key(&self) -> String {
+        match self.function.as_str() {
+            "TIME_SERIES_INTRADAY" => format!("Time Series ({})", self.interval),
+            "TIME_SERIES_DAILY" => "Time Series (Daily)".to_string(),
+            "TIME_SERIES_WEEKLY" => "Weekly Time Series".to_string(),
+            "TIME_SERIES_MONTHLY" => "Monthly Time Series".to_string(),
+            other => format!("Time Series ({})", other),
+        }
+    }
+
+    /// Parse an Alpha Vantage API response into a vector of `StockData`.
+    ///
+    /// # Arguments
+    ///
+    /// * `response` - The JSON response from the Alpha Vantage API.
+    ///
+    /// # Returns
+    ///
+    /// A Result containing a vector of StockData or a CustomError.
+    fn parse_response(&self, response: serde_json::Value) -> Result<Vec<StockData>, CustomError> {
+        let key = self.time_series_key();
+        let time_series = response[&key].as_object().ok_or(CustomError::ParseError("Invalid JSON format".into()))?;
+
+        let symbol = response["Meta Data"]["2. Symbol"].as_str().ok_or(CustomError::ParseError("Missing symbol".into()))?.to_string();
+
+        let mut stock_data = Vec::new();
+        for (timestamp, data) in time_series {
+            let open: f64 = data["1. open"].as_str().ok_or(CustomError::ParseError("Missing price".into()))?.parse().unwrap_or(0.0);
+            let high = data["2. high"].as_str().and_then(|v| v.parse().ok());
+            let low = data["3. low"].as_str().and_then(|v| v.parse().ok());
+            let close = data["4. close"].as_str().and_then(|v| v.parse().ok());
+            let volume = data["5. volume"].as_str().ok_or(CustomError::ParseError("Missing volume".into()))?.parse().unwrap_or(0);
+
+            stock_data.push(StockData {
+                symbol: symbol.clone(),
+                price: open,
+                volume,
+                timestamp: timestamp.to_string(),
+                open: Some(open),
+                high,
+                low,
+                close,
+            });
+        }
+
+        Ok(stock_data)
+    }
+}
+
+#[async_trait::async_trait]
+impl DataSource for AlphaVantage {
+    async fn fetch(&self, symbol: &str) -> Result<Vec<StockData>, CustomError> {
+        let response = reqwest::get(&self.url(symbol)).await?.json::<serde_json::Value>().await?;
+        self.parse_response(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test parsing an Alpha Vantage intraday response, including OHLC fields.
+    #[test]
+    fn test_parse_alpha_vantage_response() {
+        let json_str = r#"
+        {
+            "Meta Data": {
+                "2. Symbol": "IBM"
+            },
+            "Time Series (1min)": {
+                "2023-03-10 16:00:00": {
+                    "1. open": "123.45",
+                    "2. high": "124.00",
+                    "3. low": "123.00",
+                    "4. close": "123.90",
+                    "5. volume": "1000"
+                },
+                "2023-03-10 16:01:00": {
+                    "1. open": "123.50",
+                    "2. high": "124.10",
+                    "3. low": "123.10",
+                    "4. close": "123.95",
+                    "5. volume": "1100"
+                }
+            }
+        }
+        "#;
+        let response: serde_json::Value = serde_json::from_str(json_str).unwrap();
+        let source = AlphaVantage::new("test-key", "TIME_SERIES_INTRADAY", "1min");
+        let result = source.parse_response(response).unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].symbol, "IBM");
+        assert_eq!(result[0].close, Some(123.90));
+    }
+}
@@ -0,0 +1,152 @@
+//! Honest, out-of-sample model evaluation.
+//!
+//! `time_series_k_fold_cv` performs walk-forward cross-validation: the
+//! feature/target arrays are split into `k` contiguous, time-ordered folds
+//! and each fold (after the first) is scored after training only on the
+//! folds that precede it, so no future data ever leaks into training.
+
+use ndarray::{Array1, Array2};
+use std::collections::HashSet;
+
+use crate::classifier::{self, ModelKind};
+use crate::{calculate_accuracy, CustomError};
+use linfa::dataset::Dataset;
+
+/// Per-fold accuracy scores from a cross-validation run, plus the summary
+/// statistics computed over them.
+#[derive(Debug, Clone)]
+pub(crate) struct FoldScores {
+    pub(crate) scores: Vec<f64>,
+}
+
+impl FoldScores {
+    pub(crate) fn mean(&self) -> f64 {
+        self.scores.iter().sum::<f64>() / self.scores.len() as f64
+    }
+
+    pub(crate) fn std_dev(&self) -> f64 {
+        let mean = self.mean();
+        let variance = self.scores.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / self.scores.len() as f64;
+        variance.sqrt()
+    }
+
+    pub(crate) fn median(&self) -> f64 {
+        let mut sorted = self.scores.clone();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let mid = sorted.len() / 2;
+        if sorted.len().is_multiple_of(2) {
+            (sorted[mid - 1] + sorted[mid]) / 2.0
+        } else {
+            sorted[mid]
+        }
+    }
+
+    pub(crate) fn min(&self) -> f64 {
+        self.scores.iter().cloned().fold(f64::INFINITY, f64::min)
+    }
+
+    pub(crate) fn max(&self) -> f64 {
+        self.scores.iter().cloned().fold(f64::NEG_INFINITY, f64::max)
+    }
+}
+
+/// Run time-ordered k-fold cross-validation.
+///
+/// The data is split into `k` contiguous folds in their original order.
+/// For each fold after the first, a model is trained on every earlier fold
+/// concatenated together and scored against the held-out fold, so training
+/// data always precedes test data chronologically. A fold whose training
+/// slice contains only one class (e.g. a flat or monotone stretch) can't be
+/// fit by either classifier backend, so it is skipped rather than aborting
+/// the whole run.
+///
+/// # Arguments
+///
+/// * `features` - The full feature matrix.
+/// * `target` - The full target array.
+/// * `k` - The number of contiguous folds to split the data into (>= 2).
+/// * `model_kind` - Which classifier backend to train per fold.
+///
+/// # Returns
+///
+/// A `FoldScores` containing one accuracy score per held-out fold that was
+/// scored; skipped folds contribute no score.
+pub(crate) fn time_series_k_fold_cv(
+    features: &Array2<f64>,
+    target: &Array1<usize>,
+    k: usize,
+    model_kind: ModelKind,
+) -> Result<FoldScores, CustomError> {
+    if k < 2 {
+        return Err(CustomError::ModelError("k-fold cross-validation requires k >= 2".into()));
+    }
+
+    let n = target.len();
+    let fold_size = n / k;
+    if fold_size == 0 {
+        return Err(CustomError::ModelError("not enough samples for the requested number of folds".into()));
+    }
+
+    let mut scores = Vec::with_capacity(k - 1);
+
+    for fold in 1..k {
+        let train_end = fold * fold_size;
+        let test_end = if fold == k - 1 { n } else { train_end + fold_size };
+
+        let train_features = features.slice(ndarray::s![0..train_end, ..]).to_owned();
+        let train_target = target.slice(ndarray::s![0..train_end]).to_owned();
+        let test_features = features.slice(ndarray::s![train_end..test_end, ..]).to_owned();
+        let test_target = target.slice(ndarray::s![train_end..test_end]).to_owned();
+
+        let distinct_classes: HashSet<_> = train_target.iter().collect();
+        if distinct_classes.len() < 2 {
+            continue;
+        }
+
+        let dataset = Dataset::new(train_features, train_target);
+        let model = classifier::build_classifier(model_kind, &dataset)?;
+        let predictions = model.predict(&test_features);
+
+        scores.push(calculate_accuracy(&predictions, &test_target));
+    }
+
+    if scores.is_empty() {
+        return Err(CustomError::NotEnoughClasses);
+    }
+
+    Ok(FoldScores { scores })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    #[test]
+    fn fold_scores_summary_stats() {
+        let scores = FoldScores { scores: vec![0.5, 0.7, 0.9] };
+        assert!((scores.mean() - 0.7).abs() < 1e-9);
+        assert!((scores.median() - 0.7).abs() < 1e-9);
+        assert!((scores.min() - 0.5).abs() < 1e-9);
+        assert!((scores.max() - 0.9).abs() < 1e-9);
+    }
+
+    #[test]
+    fn k_fold_cv_rejects_too_few_folds() {
+        let features = array![[0.0], [1.0]];
+        let target = Array1::from(vec![0, 1]);
+        assert!(time_series_k_fold_cv(&features, &target, 1, ModelKind::GaussianNaiveBayes).is_err());
+    }
+
+    #[test]
+    fn k_fold_cv_skips_single_class_folds_instead_of_erroring() {
+        // The first fold's training slice is all zeros (a single class), so
+        // it should be skipped rather than aborting the whole run; the
+        // remaining fold's training slice has both classes and should score fine.
+        let features = array![[0.0], [0.1], [0.2], [1.0], [0.0], [1.1]];
+        let target = Array1::from(vec![0, 0, 0, 1, 0, 1]);
+        let result = time_series_k_fold_cv(&features, &target, 3, ModelKind::GaussianNaiveBayes);
+        assert!(result.is_ok());
+        assert!(!result.unwrap().scores.is_empty());
+    }
+}
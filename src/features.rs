@@ -0,0 +1,130 @@
+//! Feature engineering for the stock prediction pipeline.
+//!
+//! Converts a raw price series into a richer per-window feature vector by
+//! pairing a frequency-domain view of the window (via FFT) with simple
+//! statistical and technical-indicator features.
+
+use ndarray::Array2;
+use rustfft::{num_complex::Complex, FftPlanner};
+
+/// Number of leading FFT magnitude bins kept as features.
+const FFT_BINS: usize = 16;
+
+/// Default size of the sliding window used to build each feature row.
+pub(crate) const DEFAULT_WINDOW_SIZE: usize = 64;
+
+/// Period used for the "short" leg of the moving-average ratio indicator.
+const SHORT_MA_PERIOD: usize = 8;
+
+/// Number of features produced per window: FFT bins + stats + MA ratio.
+pub(crate) fn feature_len() -> usize {
+    FFT_BINS + 4 + 1
+}
+
+/// Build one feature row (FFT magnitudes, basic stats, MA ratio) from a
+/// window of prices.
+///
+/// # Arguments
+///
+/// * `window` - A slice of prices with NaNs already replaced by `0.0`.
+///
+/// # Returns
+///
+/// A `Vec<f64>` of length `feature_len()`.
+fn window_features(window: &[f64]) -> Vec<f64> {
+    let mut spectrum: Vec<Complex<f64>> = window.iter().map(|&price| Complex::new(price, 0.0)).collect();
+
+    let mut planner = FftPlanner::new();
+    let fft = planner.plan_fft_forward(spectrum.len());
+    fft.process(&mut spectrum);
+
+    let mut features = Vec::with_capacity(feature_len());
+    features.extend(spectrum.iter().take(FFT_BINS).map(|c| c.norm()));
+    // Pad with zeros if the window is shorter than the bin count.
+    features.resize(FFT_BINS, 0.0);
+
+    let mean = window.iter().sum::<f64>() / window.len() as f64;
+    let variance = window.iter().map(|price| (price - mean).powi(2)).sum::<f64>() / window.len() as f64;
+    features.push(mean);
+    features.push(variance.sqrt());
+    features.push(window.iter().cloned().fold(f64::INFINITY, f64::min));
+    features.push(window.iter().cloned().fold(f64::NEG_INFINITY, f64::max));
+
+    features.push(moving_average_ratio(window));
+
+    features
+}
+
+/// Ratio of the short-period moving average to the full-window ("long")
+/// moving average, a basic trend-strength indicator.
+fn moving_average_ratio(window: &[f64]) -> f64 {
+    let long_avg = window.iter().sum::<f64>() / window.len() as f64;
+    if long_avg == 0.0 {
+        return 0.0;
+    }
+
+    let short_len = SHORT_MA_PERIOD.min(window.len());
+    let short_avg = window[window.len() - short_len..].iter().sum::<f64>() / short_len as f64;
+
+    short_avg / long_avg
+}
+
+/// Build the full feature matrix for a price series using a sliding window.
+///
+/// `prices[i]` missing or non-finite is replaced with `0.0` before any
+/// window is built. Row `j` of the result covers the window ending at
+/// index `window_size - 1 + j` of `prices`, aligning it with the up/down
+/// target computed from the transition `prices[window_size - 1 + j] ->
+/// prices[window_size + j]`.
+///
+/// # Arguments
+///
+/// * `prices` - The raw price series.
+/// * `window_size` - The number of trailing samples used per feature row.
+///
+/// # Returns
+///
+/// An `Array2<f64>` of shape `(prices.len() - window_size, feature_len())`.
+pub(crate) fn build_feature_matrix(prices: &[f64], window_size: usize) -> Array2<f64> {
+    let cleaned: Vec<f64> = prices.iter().map(|p| if p.is_finite() { *p } else { 0.0 }).collect();
+
+    let num_windows = cleaned.len().saturating_sub(window_size);
+    let mut rows = Vec::with_capacity(num_windows);
+
+    for end in (window_size - 1)..(cleaned.len().saturating_sub(1).max(window_size - 1)) {
+        let window = &cleaned[end + 1 - window_size..=end];
+        rows.push(window_features(window));
+    }
+
+    Array2::from_shape_vec((rows.len(), feature_len()), rows.into_iter().flatten().collect())
+        .expect("row length matches feature_len()")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn feature_row_has_expected_length() {
+        let window = vec![1.0, 2.0, 3.0, 4.0];
+        assert_eq!(window_features(&window).len(), feature_len());
+    }
+
+    #[test]
+    fn build_feature_matrix_aligns_with_window_size() {
+        let prices = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let matrix = build_feature_matrix(&prices, 4);
+        assert_eq!(matrix.shape(), &[2, feature_len()]);
+    }
+
+    #[test]
+    fn nan_prices_are_replaced_with_zero() {
+        let prices = vec![1.0, f64::NAN, 3.0, 4.0, 5.0];
+        let matrix = build_feature_matrix(&prices, 4);
+        assert_eq!(matrix.shape()[0], prices.len() - 4);
+        // The only row covers [1.0, 0.0, 3.0, 4.0]: mean 2.0, min 0.0.
+        assert!(matrix.iter().all(|v| v.is_finite()));
+        assert_eq!(matrix[[0, FFT_BINS]], 2.0);
+        assert_eq!(matrix[[0, FFT_BINS + 2]], 0.0);
+    }
+}
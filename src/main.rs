@@ -5,18 +5,40 @@ extern crate ndarray;
 extern crate linfa;
 extern crate linfa_bayes;
 extern crate plotters;
+extern crate rustfft;
+extern crate linfa_logistic;
+extern crate async_trait;
+
+mod alerts;
+mod classifier;
+mod config;
+mod datasource;
+mod evaluation;
+mod features;
+mod metrics;
 
 use reqwest::Error as ReqwestError;
 use serde::Deserialize;
 use ndarray::{Array1, Array2};
-use linfa::{
-    dataset::Dataset,
-    prelude::*,
-};
-use linfa_bayes::GaussianNb;
+use linfa::dataset::Dataset;
 use plotters::prelude::*;
 use std::collections::HashSet;
 use std::convert::From;
+use std::env;
+
+use alerts::{Alert, Severity, Thresholds};
+use classifier::ModelKind;
+use config::{Config, SymbolReport};
+use datasource::{AlphaVantage, DataSource};
+use evaluation::FoldScores;
+use metrics::Metrics;
+use std::time::Instant;
+
+/// Address the Prometheus `/metrics` endpoint is served on.
+const METRICS_ADDR: &str = "0.0.0.0:9898";
+
+/// Number of contiguous folds used for time-ordered cross-validation.
+const CV_FOLDS: usize = 5;
 
 #[derive(Debug, Clone)]
 struct StockData {
@@ -24,6 +46,10 @@ struct StockData {
     price: f64,
     volume: u64,
     timestamp: String,
+    open: Option<f64>,
+    high: Option<f64>,
+    low: Option<f64>,
+    close: Option<f64>,
 }
 
 #[derive(Debug)]
@@ -31,7 +57,9 @@ enum CustomError {
     ReqwestError(reqwest::Error),
     ParseError(String),
     NotEnoughClasses,
-    NaiveBayesError(linfa_bayes::NaiveBayesError), 
+    NaiveBayesError(linfa_bayes::NaiveBayesError),
+    ModelError(String),
+    ConfigError(String),
 }
 
 impl From<reqwest::Error> for CustomError {
@@ -52,108 +80,38 @@ impl From<linfa_bayes::NaiveBayesError> for CustomError {
     }
 }
 
-/// Fetch stock data from Alpha Vantage API.
-/// 
-/// # Arguments
-///
-/// * `symbol` - The stock symbol to fetch data for.
-/// * `api_key` - The API key for accessing Alpha Vantage.
-///
-/// # Returns
-///
-/// A Result containing a vector of StockData or a CustomError.
-async fn fetch_stock_data(symbol: &str, api_key: &str) -> Result<Vec<StockData>, CustomError> {
-    let url = format!("https://www.alphavantage.co/query?function=TIME_SERIES_INTRADAY&symbol={}&interval=1min&apikey={}", symbol, api_key);
-    let response = reqwest::get(&url).await?.json::<serde_json::Value>().await?;
-    let stock_data = parse_alpha_vantage_response(response)?;
-    Ok(stock_data)
-}
-
-/// Parse Alpha Vantage API response into a vector of StockData structs.
-/// 
-/// # Arguments
-///
-/// * `response` - The JSON response from the Alpha Vantage API.
+/// Preprocess stock data into feature and target arrays for model training.
 ///
-/// # Returns
+/// Each row of the returned feature matrix is built from a sliding window of
+/// `window_size` trailing prices (FFT magnitudes, basic stats and a
+/// moving-average ratio - see the `features` module), aligned with the
+/// up/down target for the transition immediately following that window.
 ///
-/// A Result containing a vector of StockData or a CustomError.
-fn parse_alpha_vantage_response(response: serde_json::Value) -> Result<Vec<StockData>, CustomError> {
-    let time_series = response["Time Series (1min)"].as_object().ok_or(CustomError::ParseError("Invalid JSON format".into()))?;
-
-    let mut stock_data = Vec::new();
-    for (timestamp, data) in time_series {
-        let stock = StockData {
-            symbol: response["Meta Data"]["2. Symbol"].as_str().ok_or(CustomError::ParseError("Missing symbol".into()))?.to_string(),
-            price: data["1. open"].as_str().ok_or(CustomError::ParseError("Missing price".into()))?.parse().unwrap_or(0.0), // Default to 0.0 on parse failure
-            volume: data["5. volume"].as_str().ok_or(CustomError::ParseError("Missing volume".into()))?.parse().unwrap_or(0), // Default to 0 on parse failure
-            timestamp: timestamp.to_string(),
-        };
-        stock_data.push(stock);
-    }
-
-    Ok(stock_data)
-}
-
-/// Preprocess stock data into feature and target arrays for model training.
-/// 
 /// # Arguments
 ///
 /// * `data` - A vector of StockData.
+/// * `window_size` - The number of trailing price samples used per feature row.
 ///
 /// # Returns
 ///
 /// A tuple containing feature and target arrays.
-fn preprocess_data(data: Vec<StockData>) -> (Array2<f64>, Array1<usize>) {
-    let features: Vec<Vec<f64>> = data.iter().map(|stock| vec![stock.price, stock.volume as f64]).collect();
-    let mut target: Vec<usize> = Vec::new();
+fn preprocess_data(data: Vec<StockData>, window_size: usize) -> (Array2<f64>, Array1<usize>) {
+    let prices: Vec<f64> = data.iter().map(|stock| stock.price).collect();
+    let features_array = features::build_feature_matrix(&prices, window_size);
 
-    // Generate target values based on price increase (1) or decrease (0)
-    for i in 0..data.len() - 1 {
+    let mut target: Vec<usize> = Vec::new();
+    for i in (window_size - 1)..data.len() - 1 {
         if data[i].price < data[i + 1].price {
             target.push(1); // Price increased
         } else {
             target.push(0); // Price stayed the same or decreased
         }
     }
-
-    // Convert feature and target vectors to ndarray
-    let features_array = Array2::from_shape_vec((features.len(), features[0].len()), features.into_iter().flatten().collect()).unwrap();
     let target_array = Array1::from(target);
 
     (features_array, target_array)
 }
 
-/// Train a Gaussian Naive Bayes model using the provided feature and target arrays.
-/// 
-/// # Arguments
-///
-/// * `features` - The feature array.
-/// * `target` - The target array.
-///
-/// # Returns
-///
-/// A Result containing a trained GaussianNb model or a CustomError.
-fn train_model(features: &Array2<f64>, target: &Array1<usize>) -> Result<GaussianNb<f64, usize>, CustomError> {
-    let dataset = Dataset::new(features.clone(), target.clone());
-    let model = GaussianNb::params().fit(&dataset)?;
-    Ok(model)
-}
-
-/// Predict target values using the trained model.
-/// 
-/// # Arguments
-///
-/// * `model` - The trained GaussianNb model.
-/// * `features` - The feature array for making predictions.
-///
-/// # Returns
-///
-/// An array of predicted target values.
-fn predict(model: &GaussianNb<f64, usize>, features: &Array2<f64>) -> Array1<usize> {
-    model.predict(features)
-}
-
 /// Calculate the accuracy of predictions.
 /// 
 /// # Arguments
@@ -170,30 +128,33 @@ fn calculate_accuracy(predictions: &Array1<usize>, target: &Array1<usize>) -> f6
     accuracy
 }
 
-/// Generate a plot of model accuracy using the plotters crate.
-/// 
+/// Generate a histogram of per-fold cross-validation accuracy scores using
+/// the plotters crate, annotated with the mean and standard deviation.
+///
 /// # Arguments
 ///
-/// * `accuracy` - The calculated accuracy of the model.
-fn plot_accuracy(accuracy: f64) -> Result<(), Box<dyn std::error::Error>> {
-    let root_area = BitMapBackend::new("accuracy.png", (640, 480)).into_drawing_area();
+/// * `scores` - The per-fold accuracy scores from cross-validation.
+/// * `path` - Where to write the rendered PNG.
+fn plot_accuracy(scores: &FoldScores, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let root_area = BitMapBackend::new(path, (640, 480)).into_drawing_area();
     root_area.fill(&WHITE)?;
 
+    let caption = format!("Cross-Validated Accuracy (mean {:.2}% ± {:.2}%)", scores.mean() * 100.0, scores.std_dev() * 100.0);
+
     let mut chart = ChartBuilder::on(&root_area)
-        .caption("Model Accuracy", ("sans-serif", 50).into_font())
+        .caption(caption, ("sans-serif", 20).into_font())
         .margin(10)
         .x_label_area_size(30)
         .y_label_area_size(30)
-        .build_cartesian_2d(0..100, 0.0..1.0)?;
+        .build_cartesian_2d((0..scores.scores.len()).into_segmented(), 0.0..1.0)?;
 
-    chart.configure_mesh().draw()?;
+    chart.configure_mesh().y_desc("Accuracy").x_desc("Fold").draw()?;
 
-    chart.draw_series(LineSeries::new(
-        vec![(0, accuracy), (100, accuracy)],
-        &RED,
+    chart.draw_series(Histogram::vertical(&chart).style(BLUE.filled()).data(
+        scores.scores.iter().enumerate().map(|(fold, &score)| (fold, score)),
     ))?
-    .label(format!("Accuracy: {:.2}%", accuracy * 100.0))
-    .legend(|(x, y)| PathElement::new([(x, y), (x + 20, y)], &RED));
+    .label(format!("Accuracy: {:.2}% ± {:.2}%", scores.mean() * 100.0, scores.std_dev() * 100.0))
+    .legend(|(x, y)| PathElement::new([(x, y), (x + 20, y)], &BLUE));
 
     chart.configure_series_labels().background_style(&WHITE.mix(0.8)).border_style(&BLACK).draw()?;
 
@@ -205,8 +166,10 @@ fn plot_accuracy(accuracy: f64) -> Result<(), Box<dyn std::error::Error>> {
 /// # Arguments
 ///
 /// * `data` - A vector of StockData to be plotted.
-fn plot_stock_data(data: &[StockData]) -> Result<(), Box<dyn std::error::Error>> {
-    let root_area = BitMapBackend::new("stock_data.png", (640, 480)).into_drawing_area();
+/// * `alerts` - Alerts detected on `data`, overlaid as colored markers on the price chart.
+/// * `path` - Where to write the rendered PNG.
+fn plot_stock_data(data: &[StockData], alerts: &[Alert], path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let root_area = BitMapBackend::new(path, (640, 480)).into_drawing_area();
     root_area.fill(&WHITE)?;
 
     let max_price = data.iter().map(|d| d.price).fold(0./0., f64::max);
@@ -231,6 +194,13 @@ fn plot_stock_data(data: &[StockData]) -> Result<(), Box<dyn std::error::Error>>
     .label("Price")
     .legend(|(x, y)| PathElement::new([(x, y), (x + 20, y)], &BLUE));
 
+    chart.draw_series(alerts.iter().map(|alert| {
+        let color = if alert.severity == Severity::Critical { &RED } else { &MAGENTA };
+        Circle::new((alert.index, data[alert.index].price), 4, color.filled())
+    }))?
+    .label("Alert")
+    .legend(|(x, y)| Circle::new((x + 10, y), 4, RED.filled()));
+
     chart.configure_series_labels().background_style(&WHITE.mix(0.8)).border_style(&BLACK).draw()?;
 
     // Plotting stock volume
@@ -257,15 +227,32 @@ fn plot_stock_data(data: &[StockData]) -> Result<(), Box<dyn std::error::Error>>
     Ok(())
 }
 
-#[tokio::main]
-async fn main() -> Result<(), CustomError> {
-    let api_key = "IE2BY8KFGEVSA6L6";
-    let symbol = "IBM";
-
-    let stock_data = fetch_stock_data(symbol, api_key).await?;
-    println!("Fetched data: {:?}", stock_data);
+/// Run the full fetch/train/evaluate pipeline for a single symbol and
+/// produce its JSON-serializable report.
+async fn run_symbol(config: &Config, data_source: &AlphaVantage, model_kind: ModelKind, symbol: &str, metrics: &Metrics) -> Result<SymbolReport, CustomError> {
+    let fetch_started = Instant::now();
+    let stock_data = match data_source.fetch(symbol).await {
+        Ok(data) => data,
+        Err(err) => {
+            match &err {
+                CustomError::ParseError(_) => metrics.inc_parse_failures(),
+                _ => metrics.inc_fetch_failures(),
+            }
+            return Err(err);
+        }
+    };
+    metrics.record_fetch_latency(fetch_started.elapsed().as_secs_f64() * 1000.0);
+    metrics.add_rows_parsed(stock_data.len() as u64);
+    println!("Fetched data for {}: {:?}", symbol, stock_data);
+
+    let thresholds = Thresholds { upper: config.threshold_upper, lower: config.threshold_lower };
+    let mut detected_alerts = alerts::check_thresholds(&stock_data, thresholds);
+    detected_alerts.extend(alerts::detect_anomalies(&stock_data, config.anomaly_window, config.anomaly_z_threshold));
+    for alert in &detected_alerts {
+        println!("ALERT [{:?}/{:?}] {} @ {}: {}", alert.severity, alert.kind, alert.symbol, alert.timestamp, alert.value);
+    }
 
-    let (features, target) = preprocess_data(stock_data.clone());
+    let (features, target) = preprocess_data(stock_data.clone(), config.window_size);
 
     // Check if we have at least two distinct classes in the target data
     let distinct_classes: HashSet<_> = target.iter().collect();
@@ -273,59 +260,98 @@ async fn main() -> Result<(), CustomError> {
         return Err(CustomError::NotEnoughClasses);
     }
 
-    let model = train_model(&features, &target)?;
-    let predictions = predict(&model, &features);
+    let dataset = Dataset::new(features.clone(), target.clone());
+    let model = classifier::build_classifier(model_kind, &dataset)?;
+    let predictions = model.predict(&features);
+    let latest_prediction = match predictions.iter().last() {
+        Some(&1) => "up",
+        _ => "down",
+    };
+    println!("{} latest prediction: {}", symbol, latest_prediction);
+
+    let fold_scores = evaluation::time_series_k_fold_cv(&features, &target, CV_FOLDS, model_kind)?;
+    println!(
+        "{} ({:?}) cross-validated accuracy: {:.2}% ± {:.2}% over {} folds (median {:.2}%, range {:.2}%-{:.2}%)",
+        symbol,
+        model_kind,
+        fold_scores.mean() * 100.0,
+        fold_scores.std_dev() * 100.0,
+        fold_scores.scores.len(),
+        fold_scores.median() * 100.0,
+        fold_scores.min() * 100.0,
+        fold_scores.max() * 100.0,
+    );
+
+    let accuracy_plot_path = format!("accuracy_{}.png", symbol);
+    let stock_plot_path = format!("stock_data_{}.png", symbol);
+    plot_accuracy(&fold_scores, &accuracy_plot_path).expect("Failed to create accuracy plot");
+    plot_stock_data(&stock_data, &detected_alerts, &stock_plot_path).expect("Failed to create stock data plot");
+
+    metrics.set_last_accuracy(fold_scores.mean());
+    metrics.set_prediction_up_ratio(predictions.iter().filter(|&&p| p == 1).count() as f64 / predictions.len() as f64);
+    let window_start = stock_data.first().map(|d| d.timestamp.as_str()).unwrap_or("");
+    let window_end = stock_data.last().map(|d| d.timestamp.as_str()).unwrap_or("");
+    metrics.set_model_version(metrics::model_version(&format!("{:?}:{}", model_kind, config.window_size), window_start, window_end));
+
+    Ok(SymbolReport {
+        symbol: symbol.to_string(),
+        model: format!("{:?}", model_kind),
+        accuracy_mean: fold_scores.mean(),
+        accuracy_std_dev: fold_scores.std_dev(),
+        latest_prediction: latest_prediction.to_string(),
+        plot_paths: vec![accuracy_plot_path, stock_plot_path],
+    })
+}
 
-    let accuracy = calculate_accuracy(&predictions, &target);
-    println!("Naive Bayes Accuracy: {:.2}%", accuracy * 100.0);
+#[tokio::main]
+async fn main() -> Result<(), CustomError> {
+    let config_path = env::args().nth(1).unwrap_or_else(|| "config.json".to_string());
+    let config = Config::load(&config_path)?;
+    let model_kind = ModelKind::from_arg(&config.model)?;
+
+    let data_source = AlphaVantage::new(config.api_key.clone(), config.function.clone(), config.interval.clone());
+
+    let metrics = Metrics::new();
+    metrics.serve(METRICS_ADDR).map_err(|err| CustomError::ConfigError(err.to_string()))?;
+    println!("Serving Prometheus metrics on http://{}/metrics", METRICS_ADDR);
+
+    // A monitoring run never returns on its own: the exporter above only
+    // stays scrapeable for as long as the process keeps polling, so we loop
+    // over `symbols` forever, sleeping `poll_interval_secs` between passes.
+    loop {
+        let mut reports = Vec::with_capacity(config.symbols.len());
+        for symbol in &config.symbols {
+            match run_symbol(&config, &data_source, model_kind, symbol, &metrics).await {
+                Ok(report) => reports.push(report),
+                Err(err) => eprintln!("Skipping {} this pass: {:?}", symbol, err),
+            }
+        }
 
-    plot_accuracy(accuracy).expect("Failed to create accuracy plot");
-    plot_stock_data(&stock_data).expect("Failed to create stock data plot");
+        config::write_report(&reports, "results.json")?;
+        println!("Wrote results for {} symbol(s) to results.json", reports.len());
 
-    Ok(())
+        tokio::time::sleep(std::time::Duration::from_secs(config.poll_interval_secs)).await;
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    /// Test parsing Alpha Vantage API response
-    #[test]
-    fn test_parse_alpha_vantage_response() {
-        let json_str = r#"
-        {
-            "Meta Data": {
-                "2. Symbol": "IBM"
-            },
-            "Time Series (1min)": {
-                "2023-03-10 16:00:00": {
-                    "1. open": "123.45",
-                    "5. volume": "1000"
-                },
-                "2023-03-10 16:01:00": {
-                    "1. open": "123.50",
-                    "5. volume": "1100"
-                }
-            }
-        }
-        "#;
-        let response: serde_json::Value = serde_json::from_str(json_str).unwrap();
-        let result = parse_alpha_vantage_response(response).unwrap();
-        assert_eq!(result.len(), 2);
-        assert_eq!(result[0].symbol, "IBM");
-    }
-
     /// Test data preprocessing
     #[test]
     fn test_preprocess_data() {
         let stock_data = vec![
-            StockData { symbol: "IBM".to_string(), price: 123.45, volume: 1000, timestamp: "2023-03-10 16:00:00".to_string() },
-            StockData { symbol: "IBM".to_string(), price: 123.50, volume: 1100, timestamp: "2023-03-10 16:01:00".to_string() }
+            StockData { symbol: "IBM".to_string(), price: 123.45, volume: 1000, timestamp: "2023-03-10 16:00:00".to_string(), open: None, high: None, low: None, close: None },
+            StockData { symbol: "IBM".to_string(), price: 123.50, volume: 1100, timestamp: "2023-03-10 16:01:00".to_string(), open: None, high: None, low: None, close: None },
+            StockData { symbol: "IBM".to_string(), price: 123.40, volume: 1050, timestamp: "2023-03-10 16:02:00".to_string(), open: None, high: None, low: None, close: None },
+            StockData { symbol: "IBM".to_string(), price: 123.60, volume: 1200, timestamp: "2023-03-10 16:03:00".to_string(), open: None, high: None, low: None, close: None },
         ];
-        let (features, target) = preprocess_data(stock_data);
-        assert_eq!(features.shape(), &[2, 2]);
-        assert_eq!(target.len(), 1);
-        assert_eq!(target[0], 1);
+        let (features, target) = preprocess_data(stock_data, 2);
+        assert_eq!(features.shape(), &[2, features::feature_len()]);
+        assert_eq!(target.len(), 2);
+        assert_eq!(target[0], 0);
+        assert_eq!(target[1], 1);
     }
 
     /// Test accuracy calculation
@@ -0,0 +1,153 @@
+//! Runtime metrics for long-lived monitoring: counters/gauges updated as the
+//! pipeline runs, exposed over a minimal HTTP endpoint in Prometheus text
+//! exposition format so the monitor can be scraped and dashboarded.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Snapshot of the counters/gauges tracked for a monitoring run.
+#[derive(Debug, Default, Clone)]
+struct MetricsSnapshot {
+    fetch_latency_ms: f64,
+    rows_parsed: u64,
+    last_accuracy: f64,
+    prediction_up_ratio: f64,
+    parse_failures: u64,
+    fetch_failures: u64,
+    model_version: String,
+}
+
+/// Thread-safe registry of runtime metrics, shared between the polling loop
+/// and the HTTP server that exposes them.
+#[derive(Clone)]
+pub(crate) struct Metrics {
+    state: Arc<Mutex<MetricsSnapshot>>,
+}
+
+impl Metrics {
+    pub(crate) fn new() -> Self {
+        Metrics { state: Arc::new(Mutex::new(MetricsSnapshot::default())) }
+    }
+
+    pub(crate) fn record_fetch_latency(&self, millis: f64) {
+        self.state.lock().unwrap().fetch_latency_ms = millis;
+    }
+
+    pub(crate) fn add_rows_parsed(&self, rows: u64) {
+        self.state.lock().unwrap().rows_parsed += rows;
+    }
+
+    pub(crate) fn set_last_accuracy(&self, accuracy: f64) {
+        self.state.lock().unwrap().last_accuracy = accuracy;
+    }
+
+    pub(crate) fn set_prediction_up_ratio(&self, ratio: f64) {
+        self.state.lock().unwrap().prediction_up_ratio = ratio;
+    }
+
+    pub(crate) fn inc_parse_failures(&self) {
+        self.state.lock().unwrap().parse_failures += 1;
+    }
+
+    pub(crate) fn inc_fetch_failures(&self) {
+        self.state.lock().unwrap().fetch_failures += 1;
+    }
+
+    pub(crate) fn set_model_version(&self, version: String) {
+        self.state.lock().unwrap().model_version = version;
+    }
+
+    /// Render the current metrics as Prometheus text exposition format.
+    fn render(&self) -> String {
+        let snapshot = self.state.lock().unwrap().clone();
+        format!(
+            "# HELP stock_monitor_fetch_latency_ms Time to fetch the last price series, in milliseconds.\n\
+             # TYPE stock_monitor_fetch_latency_ms gauge\n\
+             stock_monitor_fetch_latency_ms {fetch_latency_ms}\n\
+             # HELP stock_monitor_rows_parsed_total Rows parsed from provider responses.\n\
+             # TYPE stock_monitor_rows_parsed_total counter\n\
+             stock_monitor_rows_parsed_total {rows_parsed}\n\
+             # HELP stock_monitor_last_accuracy Last cross-validated accuracy.\n\
+             # TYPE stock_monitor_last_accuracy gauge\n\
+             stock_monitor_last_accuracy {last_accuracy}\n\
+             # HELP stock_monitor_prediction_up_ratio Share of recent predictions labeled \"up\".\n\
+             # TYPE stock_monitor_prediction_up_ratio gauge\n\
+             stock_monitor_prediction_up_ratio {prediction_up_ratio}\n\
+             # HELP stock_monitor_parse_failures_total Parse failures encountered.\n\
+             # TYPE stock_monitor_parse_failures_total counter\n\
+             stock_monitor_parse_failures_total {parse_failures}\n\
+             # HELP stock_monitor_fetch_failures_total Fetch/transport failures encountered (network, HTTP, timeouts).\n\
+             # TYPE stock_monitor_fetch_failures_total counter\n\
+             stock_monitor_fetch_failures_total {fetch_failures}\n\
+             # HELP stock_monitor_model_info Labeled gauge tracking the trained model version; always 1.\n\
+             # TYPE stock_monitor_model_info gauge\n\
+             stock_monitor_model_info{{version=\"{model_version}\"}} 1\n",
+            fetch_latency_ms = snapshot.fetch_latency_ms,
+            rows_parsed = snapshot.rows_parsed,
+            last_accuracy = snapshot.last_accuracy,
+            prediction_up_ratio = snapshot.prediction_up_ratio,
+            parse_failures = snapshot.parse_failures,
+            fetch_failures = snapshot.fetch_failures,
+            model_version = snapshot.model_version,
+        )
+    }
+
+    /// Serve `/metrics` over plain HTTP on a background thread.
+    pub(crate) fn serve(&self, addr: &str) -> std::io::Result<()> {
+        let listener = TcpListener::bind(addr)?;
+        let metrics = self.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                metrics.handle_connection(stream);
+            }
+        });
+        Ok(())
+    }
+
+    fn handle_connection(&self, mut stream: TcpStream) {
+        let mut buf = [0u8; 1024];
+        let _ = stream.read(&mut buf);
+
+        let body = self.render();
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        let _ = stream.write_all(response.as_bytes());
+    }
+}
+
+/// Compute a short, stable "model version" identifier from the run config
+/// and the training window's timestamp range, so that re-runs trained on
+/// different configs or data windows are distinguishable as a metric label.
+pub(crate) fn model_version(config_summary: &str, window_start: &str, window_end: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    config_summary.hash(&mut hasher);
+    window_start.hash(&mut hasher);
+    window_end.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn model_version_is_stable_for_same_inputs() {
+        let a = model_version("cfg", "2023-01-01", "2023-01-02");
+        let b = model_version("cfg", "2023-01-01", "2023-01-02");
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn model_version_differs_on_window_change() {
+        let a = model_version("cfg", "2023-01-01", "2023-01-02");
+        let b = model_version("cfg", "2023-01-01", "2023-01-03");
+        assert_ne!(a, b);
+    }
+}